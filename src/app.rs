@@ -3,13 +3,96 @@ use std::{path::PathBuf, sync::Arc};
 use eframe::egui_glow;
 use egui::{mutex::Mutex, Color32, DragValue, Slider, Stroke};
 use egui_glow::glow;
-use glam::Vec3;
+use glam::{Mat4, Vec3};
 
-#[derive(serde::Deserialize, serde::Serialize, Default)]
+mod deproject;
+mod recording;
+use deproject::{deproject_depth_image, Intrinsics};
+use recording::Recorder;
+
+#[derive(serde::Deserialize, serde::Serialize)]
 pub struct CalibratorGui {
     #[serde(skip)]
     scene_3d: Option<Arc<Mutex<Scene3d>>>,
     calb_root_path: PathBuf,
+    camera: OrbitCamera,
+    point_size: f32,
+    intrinsics: Intrinsics,
+    depth_width: usize,
+    depth_height: usize,
+    #[serde(skip)]
+    pending_depth_frame: Option<Vec<f32>>,
+    #[serde(skip)]
+    recorder: Option<Recorder>,
+    use_gpu_deprojection: bool,
+    #[serde(skip)]
+    pending_model_path: Option<PathBuf>,
+}
+
+impl Default for CalibratorGui {
+    fn default() -> Self {
+        Self {
+            scene_3d: None,
+            calb_root_path: PathBuf::default(),
+            camera: OrbitCamera::default(),
+            point_size: 3.0,
+            intrinsics: Intrinsics::default(),
+            depth_width: 640,
+            depth_height: 480,
+            pending_depth_frame: None,
+            recorder: None,
+            use_gpu_deprojection: false,
+            pending_model_path: None,
+        }
+    }
+}
+
+/// A simple mouse-driven orbit camera, looking at `target` from `distance`
+/// along the direction given by `yaw`/`pitch`.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct OrbitCamera {
+    yaw: f32,
+    pitch: f32,
+    distance: f32,
+    target: Vec3,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.0,
+            distance: 5.0,
+            target: Vec3::ZERO,
+        }
+    }
+}
+
+impl OrbitCamera {
+    const MIN_DISTANCE: f32 = 0.1;
+    const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+    /// Integrate drag/scroll input from the 3D view's response into the camera state.
+    fn update(&mut self, response: &egui::Response, scroll_delta: f32) {
+        let drag = response.drag_delta();
+        self.yaw += drag.x * 0.01;
+        self.pitch = (self.pitch + drag.y * 0.01).clamp(-Self::MAX_PITCH, Self::MAX_PITCH);
+        self.distance = (self.distance - scroll_delta * 0.01).max(Self::MIN_DISTANCE);
+    }
+
+    fn eye(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        self.target + self.distance * Vec3::new(cos_pitch * cos_yaw, sin_pitch, cos_pitch * sin_yaw)
+    }
+
+    fn view_matrix(&self) -> Mat4 {
+        Mat4::look_at_rh(self.eye(), self.target, Vec3::Y)
+    }
+
+    fn projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
+        Mat4::perspective_rh_gl(45f32.to_radians(), aspect_ratio, 0.01, 1000.0)
+    }
 }
 
 impl CalibratorGui {
@@ -35,6 +118,12 @@ impl eframe::App for CalibratorGui {
     }
 
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if let Some(recorder) = &self.recorder {
+            // Frames are already persisted to disk by the capture thread; draining here just
+            // keeps its in-memory queue from growing for the lifetime of the recording.
+            let _ = recorder.drain_frames();
+        }
+
         egui::SidePanel::left("Left panel").show(ctx, |ui| self.left_panel(ui));
 
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -45,6 +134,10 @@ impl eframe::App for CalibratorGui {
     }
 
     fn on_exit(&mut self, gl: Option<&glow::Context>) {
+        if let Some(recorder) = self.recorder.take() {
+            recorder.stop();
+        }
+
         if let Some(gl) = gl {
             self.scene_3d.as_ref().unwrap().lock().destroy(gl);
         }
@@ -53,8 +146,21 @@ impl eframe::App for CalibratorGui {
 
 impl CalibratorGui {
     fn left_panel(&mut self, ui: &mut egui::Ui) {
-        if ui.button("Begin recording").clicked() {
-            todo!()
+        let recording_label = if self.recorder.is_some() {
+            "Stop recording"
+        } else {
+            "Begin recording"
+        };
+
+        if ui.button(recording_label).clicked() {
+            if let Some(recorder) = self.recorder.take() {
+                recorder.stop();
+            } else {
+                match Recorder::start(&self.calb_root_path, self.depth_width, self.depth_height) {
+                    Ok(recorder) => self.recorder = Some(recorder),
+                    Err(err) => eprintln!("Failed to start recording: {err}"),
+                }
+            }
         }
 
         let path_text = self
@@ -70,19 +176,101 @@ impl CalibratorGui {
                 self.calb_root_path = folder;
             }
         }
+
+        ui.add(Slider::new(&mut self.point_size, 1.0..=20.0).text("Point size"));
+
+        ui.separator();
+        ui.label("Camera intrinsics:");
+        ui.add(DragValue::new(&mut self.intrinsics.fx).prefix("fx: "));
+        ui.add(DragValue::new(&mut self.intrinsics.fy).prefix("fy: "));
+        ui.add(DragValue::new(&mut self.intrinsics.cx).prefix("cx: "));
+        ui.add(DragValue::new(&mut self.intrinsics.cy).prefix("cy: "));
+        ui.add(DragValue::new(&mut self.depth_width).prefix("width: "));
+        ui.add(DragValue::new(&mut self.depth_height).prefix("height: "));
+
+        if ui.button("Load frame").clicked() {
+            match self.read_depth_frame() {
+                Ok(depth) => self.pending_depth_frame = Some(depth),
+                Err(err) => eprintln!("Failed to load depth frame: {err}"),
+            }
+        }
+
+        ui.checkbox(
+            &mut self.use_gpu_deprojection,
+            "Deproject on GPU (compute shader)",
+        );
+
+        ui.separator();
+        if ui.button("Load model").clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("Wavefront OBJ", &["obj"])
+                .pick_file()
+            {
+                self.pending_model_path = Some(path);
+            }
+        }
+    }
+
+    /// Load the first frame of the most recent recording under `calb_root_path`, in the
+    /// format [`Recorder`] writes (`capture_<epoch>/frame_00000.bin` + `manifest.json`),
+    /// syncing `depth_width`/`depth_height` to the manifest's resolution.
+    fn read_depth_frame(&mut self) -> Result<Vec<f32>, std::io::Error> {
+        let (dir, manifest) = recording::latest_capture(&self.calb_root_path)?;
+        let depth = recording::read_frame(&dir, 0, &manifest)?;
+        self.depth_width = manifest.width;
+        self.depth_height = manifest.height;
+        Ok(depth)
     }
 
     fn paint_view3d(&mut self, ui: &mut egui::Ui) {
         let available_size = ui.available_size();
         let (rect, response) = ui.allocate_exact_size(available_size, egui::Sense::drag());
 
+        // Only zoom while the pointer is actually over the 3D view, so scrolling a left-panel
+        // Slider/DragValue doesn't also zoom the camera.
+        let scroll_delta = if response.hovered() {
+            ui.input(|i| i.smooth_scroll_delta.y)
+        } else {
+            0.0
+        };
+        self.camera.update(&response, scroll_delta);
+
+        let aspect_ratio = rect.width() / rect.height();
+        let view = self.camera.view_matrix();
+        let mvp = self.camera.projection_matrix(aspect_ratio) * view;
+
         // Clone locals so we can move them into the paint callback:
-        let rotating_triangle = self.scene_3d.clone().unwrap();
+        let scene_3d = self.scene_3d.clone().unwrap();
+        let point_size = self.point_size;
+        let pending_depth_frame = self.pending_depth_frame.take();
+        let pending_model_path = self.pending_model_path.take();
+        let intrinsics = self.intrinsics;
+        let depth_width = self.depth_width;
+        let depth_height = self.depth_height;
+        let use_gpu_deprojection = self.use_gpu_deprojection;
 
         let cb = egui_glow::CallbackFn::new(move |_info, painter| {
-            rotating_triangle
-                .lock()
-                .paint(painter.gl(), 0.);
+            let mut scene_3d = scene_3d.lock();
+            let gl = painter.gl();
+
+            if let Some(depth) = &pending_depth_frame {
+                let deprojected_on_gpu = use_gpu_deprojection
+                    && scene_3d.deproject_gpu(gl, depth, depth_width, depth_height, intrinsics);
+                if !deprojected_on_gpu {
+                    let points =
+                        deproject_depth_image(depth, depth_width, depth_height, intrinsics);
+                    let colors = vec![Color32::WHITE; points.len()];
+                    scene_3d.upload_points(gl, &points, &colors);
+                }
+            }
+
+            if let Some(path) = &pending_model_path {
+                if let Err(err) = scene_3d.load_obj(gl, path) {
+                    eprintln!("Failed to load reference model {}: {err}", path.display());
+                }
+            }
+
+            scene_3d.paint(gl, mvp, view, point_size);
         });
 
         let callback = egui::PaintCallback {
@@ -93,9 +281,102 @@ impl CalibratorGui {
     }
 }
 
+/// A single point-cloud vertex: position plus packed RGBA color.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct PointVertex {
+    position: [f32; 3],
+    color: [u8; 4],
+}
+
+/// A point as written by the GPU deprojection compute shader: `position.w` is
+/// used as a validity flag (`1.0` = valid, `0.0` = skipped) since the compute
+/// shader writes a fixed-size buffer and can't vary the number of invocations.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuPoint {
+    position: [f32; 4],
+    color: [f32; 4],
+}
+
+/// A reference-mesh vertex: position plus normal, used for Lambertian shading.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct MeshVertex {
+    position: [f32; 3],
+    normal: [f32; 3],
+}
+
+const COMPUTE_SHADER_SOURCE: &str = r#"
+    #version 430
+    layout(local_size_x = 16, local_size_y = 16) in;
+
+    layout(r32f, binding = 0) readonly uniform image2D u_depth;
+
+    struct GpuPoint {
+        vec4 position;
+        vec4 color;
+    };
+
+    layout(std430, binding = 1) buffer PointsBuffer {
+        GpuPoint points[];
+    };
+
+    uniform ivec2 u_size;
+    uniform float u_fx;
+    uniform float u_fy;
+    uniform float u_cx;
+    uniform float u_cy;
+
+    void main() {
+        ivec2 uv = ivec2(gl_GlobalInvocationID.xy);
+        if (uv.x >= u_size.x || uv.y >= u_size.y) {
+            return;
+        }
+
+        float d = imageLoad(u_depth, uv).r;
+        vec3 position = vec3(
+            (float(uv.x) - u_cx) * d / u_fx,
+            (float(uv.y) - u_cy) * d / u_fy,
+            d
+        );
+
+        int index = uv.y * u_size.x + uv.x;
+        points[index].position = vec4(position, d > 0.0 ? 1.0 : 0.0);
+        points[index].color = vec4(1.0);
+    }
+"#;
+
 struct Scene3d {
     program: glow::Program,
     vertex_array: glow::VertexArray,
+    points_buffer: glow::Buffer,
+    num_points: usize,
+
+    /// GPU compute-shader deprojection path. Compiled out on wasm32 (WebGL has no compute
+    /// shaders) and `None` on drivers that don't support them; callers fall back to the CPU path.
+    #[cfg(not(target_arch = "wasm32"))]
+    compute: Option<ComputeDeprojector>,
+    /// Whether the last-uploaded point cloud came from the GPU compute path, and
+    /// if so, how many points it wrote.
+    #[cfg(not(target_arch = "wasm32"))]
+    gpu_num_points: Option<usize>,
+
+    /// Reference mesh loaded via [`Self::load_obj`], rendered alongside the point cloud.
+    mesh_program: glow::Program,
+    mesh_vertex_array: glow::VertexArray,
+    mesh_vertex_buffer: glow::Buffer,
+    mesh_index_buffer: glow::Buffer,
+    mesh_index_count: usize,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct ComputeDeprojector {
+    program: glow::Program,
+    depth_texture: glow::Texture,
+    gpu_points_buffer: glow::Buffer,
+    gpu_vertex_array: glow::VertexArray,
+    texture_size: (usize, usize),
 }
 
 #[allow(unsafe_code)] // we need unsafe code to use glow
@@ -114,22 +395,23 @@ impl Scene3d {
 
             let (vertex_shader_source, fragment_shader_source) = (
                 r#"
-                    const vec2 verts[3] = vec2[3](
-                        vec2(0.0, 1.0),
-                        vec2(-1.0, -1.0),
-                        vec2(1.0, -1.0)
-                    );
-                    const vec4 colors[3] = vec4[3](
-                        vec4(1.0, 0.0, 0.0, 1.0),
-                        vec4(0.0, 1.0, 0.0, 1.0),
-                        vec4(0.0, 0.0, 1.0, 1.0)
-                    );
+                    in vec4 a_position;
+                    in vec4 a_color;
                     out vec4 v_color;
-                    uniform float u_angle;
+                    uniform mat4 u_mvp;
+                    uniform float u_point_size;
                     void main() {
-                        v_color = colors[gl_VertexID];
-                        gl_Position = vec4(verts[gl_VertexID], 0.0, 1.0);
-                        gl_Position.x *= cos(u_angle);
+                        v_color = a_color;
+                        // The GPU compute-deprojection path packs a validity flag into
+                        // position.w (0.0 = skipped, d == 0 pixel); push those off-clip-space
+                        // so they don't draw as a spurious cloud of points at the origin. CPU-
+                        // path vertices only supply xyz, so w defaults to 1.0 and always pass.
+                        if (a_position.w == 0.0) {
+                            gl_Position = vec4(2.0, 2.0, 2.0, 1.0);
+                        } else {
+                            gl_Position = u_mvp * vec4(a_position.xyz, 1.0);
+                        }
+                        gl_PointSize = u_point_size;
                     }
                 "#,
                 r#"
@@ -176,32 +458,510 @@ impl Scene3d {
             let vertex_array = gl
                 .create_vertex_array()
                 .expect("Cannot create vertex array");
+            let points_buffer = gl.create_buffer().expect("Cannot create buffer");
+
+            let stride = std::mem::size_of::<PointVertex>() as i32;
+            gl.bind_vertex_array(Some(vertex_array));
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(points_buffer));
+
+            let position_location = gl.get_attrib_location(program, "a_position").unwrap();
+            gl.vertex_attrib_pointer_f32(position_location, 3, glow::FLOAT, false, stride, 0);
+            gl.enable_vertex_attrib_array(position_location);
+
+            let color_location = gl.get_attrib_location(program, "a_color").unwrap();
+            gl.vertex_attrib_pointer_f32(color_location, 4, glow::UNSIGNED_BYTE, true, stride, 12);
+            gl.enable_vertex_attrib_array(color_location);
+
+            gl.bind_vertex_array(None);
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+
+            let (mesh_program, mesh_vertex_array, mesh_vertex_buffer, mesh_index_buffer) =
+                Self::create_mesh_pipeline(gl, shader_version);
+
+            // Desktop core-profile GL ignores `gl_PointSize` writes in the shader unless
+            // this cap is enabled; WebGL/GLES always honor it and lack the enum.
+            #[cfg(not(target_arch = "wasm32"))]
+            gl.enable(glow::PROGRAM_POINT_SIZE);
 
             Self {
                 program,
                 vertex_array,
+                points_buffer,
+                num_points: 0,
+                #[cfg(not(target_arch = "wasm32"))]
+                compute: ComputeDeprojector::try_new(gl, program),
+                #[cfg(not(target_arch = "wasm32"))]
+                gpu_num_points: None,
+                mesh_program,
+                mesh_vertex_array,
+                mesh_vertex_buffer,
+                mesh_index_buffer,
+                mesh_index_count: 0,
             }
         }
     }
 
+    /// Compile the Lambertian mesh shader and set up its vertex/index buffers.
+    unsafe fn create_mesh_pipeline(
+        gl: &glow::Context,
+        shader_version: &str,
+    ) -> (glow::Program, glow::VertexArray, glow::Buffer, glow::Buffer) {
+        use glow::HasContext as _;
+
+        let program = gl.create_program().expect("Cannot create program");
+
+        let (vertex_shader_source, fragment_shader_source) = (
+            r#"
+                in vec3 a_position;
+                in vec3 a_normal;
+                out vec3 v_normal;
+                uniform mat4 u_mvp;
+                uniform mat4 u_view;
+                void main() {
+                    v_normal = mat3(u_view) * a_normal;
+                    gl_Position = u_mvp * vec4(a_position, 1.0);
+                }
+            "#,
+            r#"
+                precision mediump float;
+                in vec3 v_normal;
+                out vec4 out_color;
+                void main() {
+                    vec3 normal = normalize(v_normal);
+                    vec3 light_dir = normalize(vec3(0.4, 0.6, 0.7));
+                    float diffuse = max(dot(normal, light_dir), 0.0);
+                    out_color = vec4(vec3(0.2) + vec3(0.7) * diffuse, 1.0);
+                }
+            "#,
+        );
+
+        let shader_sources = [
+            (glow::VERTEX_SHADER, vertex_shader_source),
+            (glow::FRAGMENT_SHADER, fragment_shader_source),
+        ];
+
+        let shaders: Vec<_> = shader_sources
+            .iter()
+            .map(|(shader_type, shader_source)| {
+                let shader = gl
+                    .create_shader(*shader_type)
+                    .expect("Cannot create shader");
+                gl.shader_source(shader, &format!("{}\n{}", shader_version, shader_source));
+                gl.compile_shader(shader);
+                if !gl.get_shader_compile_status(shader) {
+                    panic!("{}", gl.get_shader_info_log(shader));
+                }
+                gl.attach_shader(program, shader);
+                shader
+            })
+            .collect();
+
+        gl.link_program(program);
+        if !gl.get_program_link_status(program) {
+            panic!("{}", gl.get_program_info_log(program));
+        }
+
+        for shader in shaders {
+            gl.detach_shader(program, shader);
+            gl.delete_shader(shader);
+        }
+
+        let vertex_array = gl
+            .create_vertex_array()
+            .expect("Cannot create vertex array");
+        let vertex_buffer = gl.create_buffer().expect("Cannot create buffer");
+        let index_buffer = gl.create_buffer().expect("Cannot create buffer");
+
+        let stride = std::mem::size_of::<MeshVertex>() as i32;
+        gl.bind_vertex_array(Some(vertex_array));
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(vertex_buffer));
+        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(index_buffer));
+
+        let position_location = gl.get_attrib_location(program, "a_position").unwrap();
+        gl.vertex_attrib_pointer_f32(position_location, 3, glow::FLOAT, false, stride, 0);
+        gl.enable_vertex_attrib_array(position_location);
+
+        let normal_location = gl.get_attrib_location(program, "a_normal").unwrap();
+        gl.vertex_attrib_pointer_f32(normal_location, 3, glow::FLOAT, false, stride, 12);
+        gl.enable_vertex_attrib_array(normal_location);
+
+        gl.bind_vertex_array(None);
+        gl.bind_buffer(glow::ARRAY_BUFFER, None);
+        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+
+        (program, vertex_array, vertex_buffer, index_buffer)
+    }
+
+    /// Load a reference OBJ mesh from disk and upload it for rendering alongside the point cloud.
+    fn load_obj(
+        &mut self,
+        gl: &glow::Context,
+        path: &std::path::Path,
+    ) -> Result<(), tobj::LoadError> {
+        use glow::HasContext as _;
+
+        let (models, _materials) = tobj::load_obj(
+            path,
+            &tobj::LoadOptions {
+                triangulate: true,
+                single_index: true,
+                ..Default::default()
+            },
+        )?;
+
+        let Some(model) = models.into_iter().next() else {
+            return Ok(());
+        };
+        let mesh = model.mesh;
+
+        let has_normals = mesh.normals.len() == mesh.positions.len();
+        let vertices: Vec<MeshVertex> = (0..mesh.positions.len() / 3)
+            .map(|i| MeshVertex {
+                position: [
+                    mesh.positions[3 * i],
+                    mesh.positions[3 * i + 1],
+                    mesh.positions[3 * i + 2],
+                ],
+                normal: if has_normals {
+                    [
+                        mesh.normals[3 * i],
+                        mesh.normals[3 * i + 1],
+                        mesh.normals[3 * i + 2],
+                    ]
+                } else {
+                    [0.0, 0.0, 1.0]
+                },
+            })
+            .collect();
+
+        unsafe {
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.mesh_vertex_buffer));
+            gl.buffer_data_u8_slice(
+                glow::ARRAY_BUFFER,
+                bytemuck::cast_slice(&vertices),
+                glow::STATIC_DRAW,
+            );
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.mesh_index_buffer));
+            gl.buffer_data_u8_slice(
+                glow::ELEMENT_ARRAY_BUFFER,
+                bytemuck::cast_slice(&mesh.indices),
+                glow::STATIC_DRAW,
+            );
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+        }
+
+        self.mesh_index_count = mesh.indices.len();
+        Ok(())
+    }
+
+    /// Upload a colored point cloud, replacing whatever was previously shown.
+    fn upload_points(&mut self, gl: &glow::Context, positions: &[Vec3], colors: &[Color32]) {
+        use glow::HasContext as _;
+
+        assert_eq!(positions.len(), colors.len());
+
+        let vertices: Vec<PointVertex> = positions
+            .iter()
+            .zip(colors)
+            .map(|(p, c)| PointVertex {
+                position: [p.x, p.y, p.z],
+                color: c.to_array(),
+            })
+            .collect();
+
+        unsafe {
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.points_buffer));
+            gl.buffer_data_u8_slice(
+                glow::ARRAY_BUFFER,
+                bytemuck::cast_slice(&vertices),
+                glow::DYNAMIC_DRAW,
+            );
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+        }
+
+        self.num_points = vertices.len();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.gpu_num_points = None;
+        }
+    }
+
+    /// Deproject `depth` directly into the points buffer on the GPU via a compute shader,
+    /// skipping the CPU round-trip. Returns `false` (and uploads nothing) when the compute
+    /// path isn't available, so callers can fall back to [`Self::upload_points`].
+    #[cfg(not(target_arch = "wasm32"))]
+    fn deproject_gpu(
+        &mut self,
+        gl: &glow::Context,
+        depth: &[f32],
+        width: usize,
+        height: usize,
+        intrinsics: Intrinsics,
+    ) -> bool {
+        let Some(compute) = &mut self.compute else {
+            return false;
+        };
+        compute.dispatch(gl, depth, width, height, intrinsics);
+        self.gpu_num_points = Some(width * height);
+        true
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn deproject_gpu(
+        &mut self,
+        _gl: &glow::Context,
+        _depth: &[f32],
+        _width: usize,
+        _height: usize,
+        _intrinsics: Intrinsics,
+    ) -> bool {
+        false
+    }
+
     fn destroy(&self, gl: &glow::Context) {
         use glow::HasContext as _;
         unsafe {
             gl.delete_program(self.program);
             gl.delete_vertex_array(self.vertex_array);
+            gl.delete_buffer(self.points_buffer);
+            gl.delete_program(self.mesh_program);
+            gl.delete_vertex_array(self.mesh_vertex_array);
+            gl.delete_buffer(self.mesh_vertex_buffer);
+            gl.delete_buffer(self.mesh_index_buffer);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(compute) = &self.compute {
+            compute.destroy(gl);
         }
     }
 
-    fn paint(&self, gl: &glow::Context, angle: f32) {
+    fn paint(&self, gl: &glow::Context, mvp: Mat4, view: Mat4, point_size: f32) {
         use glow::HasContext as _;
         unsafe {
+            // Without this, the mesh (drawn after the points) always wins regardless of
+            // which is actually closer to the camera, and overlapping points don't resolve
+            // either. Enabled on every paint because egui's painter disables depth testing
+            // before each frame and callback. Requires the windowing layer to request a
+            // depth buffer for the context.
+            gl.enable(glow::DEPTH_TEST);
+            gl.clear(glow::DEPTH_BUFFER_BIT);
+
             gl.use_program(Some(self.program));
+            gl.uniform_matrix_4_f32_slice(
+                gl.get_uniform_location(self.program, "u_mvp").as_ref(),
+                false,
+                &mvp.to_cols_array(),
+            );
             gl.uniform_1_f32(
-                gl.get_uniform_location(self.program, "u_angle").as_ref(),
-                angle,
+                gl.get_uniform_location(self.program, "u_point_size")
+                    .as_ref(),
+                point_size,
             );
-            gl.bind_vertex_array(Some(self.vertex_array));
-            gl.draw_arrays(glow::TRIANGLES, 0, 3);
+
+            let mut drew_gpu_points = false;
+            #[cfg(not(target_arch = "wasm32"))]
+            if let (Some(compute), Some(gpu_num_points)) = (&self.compute, self.gpu_num_points) {
+                gl.bind_vertex_array(Some(compute.gpu_vertex_array));
+                gl.draw_arrays(glow::POINTS, 0, gpu_num_points as i32);
+                drew_gpu_points = true;
+            }
+
+            if !drew_gpu_points {
+                gl.bind_vertex_array(Some(self.vertex_array));
+                gl.draw_arrays(glow::POINTS, 0, self.num_points as i32);
+            }
+
+            if self.mesh_index_count > 0 {
+                gl.use_program(Some(self.mesh_program));
+                gl.uniform_matrix_4_f32_slice(
+                    gl.get_uniform_location(self.mesh_program, "u_mvp").as_ref(),
+                    false,
+                    &mvp.to_cols_array(),
+                );
+                gl.uniform_matrix_4_f32_slice(
+                    gl.get_uniform_location(self.mesh_program, "u_view")
+                        .as_ref(),
+                    false,
+                    &view.to_cols_array(),
+                );
+                gl.bind_vertex_array(Some(self.mesh_vertex_array));
+                gl.draw_elements(
+                    glow::TRIANGLES,
+                    self.mesh_index_count as i32,
+                    glow::UNSIGNED_INT,
+                    0,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(unsafe_code)]
+impl ComputeDeprojector {
+    /// Compile the deprojection compute shader and allocate its resources, returning `None`
+    /// if compute shaders aren't supported (or fail to compile) on this GL context.
+    /// `point_program` is the point-cloud rendering program whose `a_position`/`a_color`
+    /// attribute locations `gpu_vertex_array` must match.
+    fn try_new(gl: &glow::Context, point_program: glow::Program) -> Option<Self> {
+        use glow::HasContext as _;
+
+        unsafe {
+            let program = gl.create_program().ok()?;
+            let shader = gl.create_shader(glow::COMPUTE_SHADER).ok()?;
+            gl.shader_source(shader, COMPUTE_SHADER_SOURCE);
+            gl.compile_shader(shader);
+            if !gl.get_shader_compile_status(shader) {
+                eprintln!(
+                    "GPU deprojection unavailable, compute shader failed to compile: {}",
+                    gl.get_shader_info_log(shader)
+                );
+                gl.delete_shader(shader);
+                gl.delete_program(program);
+                return None;
+            }
+            gl.attach_shader(program, shader);
+            gl.link_program(program);
+            gl.detach_shader(program, shader);
+            gl.delete_shader(shader);
+            if !gl.get_program_link_status(program) {
+                eprintln!(
+                    "GPU deprojection unavailable, compute program failed to link: {}",
+                    gl.get_program_info_log(program)
+                );
+                gl.delete_program(program);
+                return None;
+            }
+
+            let depth_texture = gl.create_texture().ok()?;
+            let gpu_points_buffer = gl.create_buffer().ok()?;
+            let gpu_vertex_array = gl.create_vertex_array().ok()?;
+
+            let position_location = gl.get_attrib_location(point_program, "a_position")?;
+            let color_location = gl.get_attrib_location(point_program, "a_color")?;
+
+            let stride = std::mem::size_of::<GpuPoint>() as i32;
+            gl.bind_vertex_array(Some(gpu_vertex_array));
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(gpu_points_buffer));
+            // 4 components (not 3): the vertex shader reads position.w as the validity flag
+            // the compute shader wrote, so it must actually reach the shader.
+            gl.vertex_attrib_pointer_f32(position_location, 4, glow::FLOAT, false, stride, 0);
+            gl.enable_vertex_attrib_array(position_location);
+            gl.vertex_attrib_pointer_f32(color_location, 4, glow::FLOAT, false, stride, 16);
+            gl.enable_vertex_attrib_array(color_location);
+            gl.bind_vertex_array(None);
+            gl.bind_buffer(glow::ARRAY_BUFFER, None);
+
+            Some(Self {
+                program,
+                depth_texture,
+                gpu_points_buffer,
+                gpu_vertex_array,
+                texture_size: (0, 0),
+            })
+        }
+    }
+
+    fn dispatch(
+        &mut self,
+        gl: &glow::Context,
+        depth: &[f32],
+        width: usize,
+        height: usize,
+        intrinsics: Intrinsics,
+    ) {
+        use glow::HasContext as _;
+
+        unsafe {
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.depth_texture));
+            if self.texture_size != (width, height) {
+                gl.tex_image_2d(
+                    glow::TEXTURE_2D,
+                    0,
+                    glow::R32F as i32,
+                    width as i32,
+                    height as i32,
+                    0,
+                    glow::RED,
+                    glow::FLOAT,
+                    Some(bytemuck::cast_slice(depth)),
+                );
+                self.texture_size = (width, height);
+            } else {
+                gl.tex_sub_image_2d(
+                    glow::TEXTURE_2D,
+                    0,
+                    0,
+                    0,
+                    width as i32,
+                    height as i32,
+                    glow::RED,
+                    glow::FLOAT,
+                    glow::PixelUnpackData::Slice(Some(bytemuck::cast_slice(depth))),
+                );
+            }
+
+            let buffer_size = width * height * std::mem::size_of::<GpuPoint>();
+            gl.bind_buffer(glow::SHADER_STORAGE_BUFFER, Some(self.gpu_points_buffer));
+            gl.buffer_data_size(
+                glow::SHADER_STORAGE_BUFFER,
+                buffer_size as i32,
+                glow::DYNAMIC_COPY,
+            );
+
+            gl.use_program(Some(self.program));
+            gl.bind_image_texture(
+                0,
+                self.depth_texture,
+                0,
+                false,
+                0,
+                glow::READ_ONLY,
+                glow::R32F,
+            );
+            gl.bind_buffer_base(glow::SHADER_STORAGE_BUFFER, 1, Some(self.gpu_points_buffer));
+            gl.uniform_2_i32(
+                gl.get_uniform_location(self.program, "u_size").as_ref(),
+                width as i32,
+                height as i32,
+            );
+            gl.uniform_1_f32(
+                gl.get_uniform_location(self.program, "u_fx").as_ref(),
+                intrinsics.fx,
+            );
+            gl.uniform_1_f32(
+                gl.get_uniform_location(self.program, "u_fy").as_ref(),
+                intrinsics.fy,
+            );
+            gl.uniform_1_f32(
+                gl.get_uniform_location(self.program, "u_cx").as_ref(),
+                intrinsics.cx,
+            );
+            gl.uniform_1_f32(
+                gl.get_uniform_location(self.program, "u_cy").as_ref(),
+                intrinsics.cy,
+            );
+
+            let workgroups_x = (width as u32).div_ceil(16);
+            let workgroups_y = (height as u32).div_ceil(16);
+            gl.dispatch_compute(workgroups_x, workgroups_y, 1);
+            gl.memory_barrier(
+                glow::SHADER_STORAGE_BARRIER_BIT | glow::VERTEX_ATTRIB_ARRAY_BARRIER_BIT,
+            );
+
+            gl.bind_buffer(glow::SHADER_STORAGE_BUFFER, None);
+            gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+    }
+
+    fn destroy(&self, gl: &glow::Context) {
+        use glow::HasContext as _;
+        unsafe {
+            gl.delete_program(self.program);
+            gl.delete_texture(self.depth_texture);
+            gl.delete_buffer(self.gpu_points_buffer);
+            gl.delete_vertex_array(self.gpu_vertex_array);
         }
     }
 }
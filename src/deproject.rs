@@ -0,0 +1,81 @@
+use glam::Vec3;
+
+/// Pinhole camera intrinsics used to deproject a depth buffer into 3D points.
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+pub struct Intrinsics {
+    pub fx: f32,
+    pub fy: f32,
+    pub cx: f32,
+    pub cy: f32,
+}
+
+impl Default for Intrinsics {
+    fn default() -> Self {
+        Self {
+            fx: 500.0,
+            fy: 500.0,
+            cx: 320.0,
+            cy: 240.0,
+        }
+    }
+}
+
+/// Deproject a row-major depth buffer (`width * height` samples, in meters) into camera-space
+/// points using pinhole `intrinsics`. Pixels with `depth == 0.0` are invalid and skipped.
+pub fn deproject_depth_image(
+    depth: &[f32],
+    width: usize,
+    height: usize,
+    intrinsics: Intrinsics,
+) -> Vec<Vec3> {
+    let mut points = Vec::new();
+    for v in 0..height {
+        for u in 0..width {
+            let d = depth[v * width + u];
+            if d == 0.0 {
+                continue;
+            }
+            let x = (u as f32 - intrinsics.cx) * d / intrinsics.fx;
+            let y = (v as f32 - intrinsics.cy) * d / intrinsics.fy;
+            points.push(Vec3::new(x, y, d));
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_depth_is_skipped() {
+        let intrinsics = Intrinsics {
+            fx: 1.0,
+            fy: 1.0,
+            cx: 0.0,
+            cy: 0.0,
+        };
+        let depth = [0.0, 1.0];
+        let points = deproject_depth_image(&depth, 2, 1, intrinsics);
+        assert_eq!(points.len(), 1);
+    }
+
+    #[test]
+    fn known_pixel_maps_to_expected_point() {
+        let intrinsics = Intrinsics {
+            fx: 100.0,
+            fy: 100.0,
+            cx: 50.0,
+            cy: 25.0,
+        };
+        let mut depth = vec![0.0; 4 * 2];
+        depth[1 * 4 + 2] = 2.0; // u = 2, v = 1
+        let points = deproject_depth_image(&depth, 4, 2, intrinsics);
+
+        assert_eq!(points.len(), 1);
+        let p = points[0];
+        assert!((p.x - (2.0 - 50.0) * 2.0 / 100.0).abs() < 1e-6);
+        assert!((p.y - (1.0 - 25.0) * 2.0 / 100.0).abs() < 1e-6);
+        assert_eq!(p.z, 2.0);
+    }
+}
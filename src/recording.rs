@@ -0,0 +1,241 @@
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// A single captured depth frame, handed off from the capture thread to anyone
+/// consuming frames live (e.g. a future preview pane).
+pub struct CapturedFrame {
+    pub index: usize,
+    pub depth: Vec<f32>,
+    pub timestamp_ms: u64,
+}
+
+/// Manifest written alongside the captured frames so loaders know how to
+/// interpret the sequentially numbered frame files.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Manifest {
+    pub frame_count: usize,
+    pub width: usize,
+    pub height: usize,
+    pub timestamps_ms: Vec<u64>,
+}
+
+/// Find the most recently started `capture_*` directory under `root` and load its manifest,
+/// so a loader can read back what [`Recorder`] wrote without guessing a resolution.
+pub fn latest_capture(root: &Path) -> std::io::Result<(PathBuf, Manifest)> {
+    let mut newest: Option<(u64, PathBuf)> = None;
+    for entry in std::fs::read_dir(root)? {
+        let path = entry?.path();
+        let Some(epoch_secs) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|name| name.strip_prefix("capture_"))
+            .and_then(|rest| rest.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        if newest.as_ref().is_none_or(|(best, _)| epoch_secs > *best) {
+            newest = Some((epoch_secs, path));
+        }
+    }
+
+    let (_, dir) = newest.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no capture_* directory found under {}", root.display()),
+        )
+    })?;
+    let manifest_bytes = std::fs::read(dir.join("manifest.json"))?;
+    let manifest: Manifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+    Ok((dir, manifest))
+}
+
+/// Read frame `index` (as written by [`capture_loop`]) out of `dir`, validating its length
+/// against `manifest`'s resolution rather than trusting the file blindly.
+pub fn read_frame(dir: &Path, index: usize, manifest: &Manifest) -> std::io::Result<Vec<f32>> {
+    let bytes = std::fs::read(dir.join(format!("frame_{index:05}.bin")))?;
+    let expected_len = manifest.width * manifest.height * 4;
+    if bytes.len() != expected_len {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "frame_{index:05}.bin is {} bytes, expected {expected_len} for {}x{}",
+                bytes.len(),
+                manifest.width,
+                manifest.height
+            ),
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+/// Captures depth frames into a timestamped subdirectory of `root` on a
+/// background thread, so the egui update loop never blocks on capture or disk I/O.
+pub struct Recorder {
+    stop_flag: Arc<AtomicBool>,
+    frame_queue: Arc<Mutex<VecDeque<CapturedFrame>>>,
+    handle: Option<JoinHandle<()>>,
+    output_dir: PathBuf,
+}
+
+impl Recorder {
+    pub fn start(root: &Path, width: usize, height: usize) -> std::io::Result<Self> {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock is before the epoch");
+        let output_dir = root.join(format!("capture_{}", since_epoch.as_secs()));
+        std::fs::create_dir_all(&output_dir)?;
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let frame_queue = Arc::new(Mutex::new(VecDeque::new()));
+
+        let thread_stop = stop_flag.clone();
+        let thread_queue = frame_queue.clone();
+        let thread_dir = output_dir.clone();
+        let handle = std::thread::spawn(move || {
+            capture_loop(thread_stop, thread_queue, thread_dir, width, height);
+        });
+
+        Ok(Self {
+            stop_flag,
+            frame_queue,
+            handle: Some(handle),
+            output_dir,
+        })
+    }
+
+    /// Signal the capture thread to stop and wait for it to flush its manifest.
+    pub fn stop(mut self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+
+    /// Drain any frames the capture thread has produced since the last call.
+    pub fn drain_frames(&self) -> Vec<CapturedFrame> {
+        self.frame_queue.lock().unwrap().drain(..).collect()
+    }
+
+    pub fn output_dir(&self) -> &Path {
+        &self.output_dir
+    }
+}
+
+fn capture_loop(
+    stop_flag: Arc<AtomicBool>,
+    frame_queue: Arc<Mutex<VecDeque<CapturedFrame>>>,
+    output_dir: PathBuf,
+    width: usize,
+    height: usize,
+) {
+    let start = Instant::now();
+    let mut timestamps_ms = Vec::new();
+    let mut index = 0;
+
+    while !stop_flag.load(Ordering::Relaxed) {
+        let depth = capture_depth_frame(width, height);
+        let timestamp_ms = start.elapsed().as_millis() as u64;
+
+        let frame_path = output_dir.join(format!("frame_{index:05}.bin"));
+        let frame_bytes: Vec<u8> = depth.iter().flat_map(|d| d.to_le_bytes()).collect();
+        if let Err(err) = std::fs::write(&frame_path, &frame_bytes) {
+            eprintln!("Failed to write {}: {err}", frame_path.display());
+            break;
+        }
+
+        timestamps_ms.push(timestamp_ms);
+        frame_queue.lock().unwrap().push_back(CapturedFrame {
+            index,
+            depth,
+            timestamp_ms,
+        });
+
+        index += 1;
+        std::thread::sleep(std::time::Duration::from_millis(33));
+    }
+
+    let manifest = Manifest {
+        frame_count: index,
+        width,
+        height,
+        timestamps_ms,
+    };
+    if let Ok(json) = serde_json::to_string_pretty(&manifest) {
+        if let Err(err) = std::fs::write(output_dir.join("manifest.json"), json) {
+            eprintln!("Failed to write capture manifest: {err}");
+        }
+    }
+}
+
+/// Placeholder for the real depth/color capture device. Returns an all-invalid
+/// (all-zero) depth buffer until a real sensor is wired in.
+fn capture_depth_frame(width: usize, height: usize) -> Vec<f32> {
+    vec![0.0; width * height]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_temp_dir(label: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("deproject_gui_test_{label}_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn latest_capture_picks_the_highest_epoch_dir() {
+        let root = unique_temp_dir("latest_capture");
+        for epoch in [100, 300, 200] {
+            let dir = root.join(format!("capture_{epoch}"));
+            std::fs::create_dir_all(&dir).unwrap();
+            let manifest = Manifest {
+                frame_count: 0,
+                width: epoch,
+                height: 1,
+                timestamps_ms: vec![],
+            };
+            std::fs::write(
+                dir.join("manifest.json"),
+                serde_json::to_string(&manifest).unwrap(),
+            )
+            .unwrap();
+        }
+
+        let (dir, manifest) = latest_capture(&root).unwrap();
+        assert_eq!(dir, root.join("capture_300"));
+        assert_eq!(manifest.width, 300);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn read_frame_rejects_a_short_file() {
+        let root = unique_temp_dir("read_frame");
+        std::fs::write(root.join("frame_00000.bin"), [0u8; 4]).unwrap();
+        let manifest = Manifest {
+            frame_count: 1,
+            width: 4,
+            height: 4,
+            timestamps_ms: vec![0],
+        };
+
+        let err = read_frame(&root, 0, &manifest).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}